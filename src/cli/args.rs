@@ -1,6 +1,9 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use clap::Parser;
+use std::io::IsTerminal;
+use std::path::Path;
 
+use crate::cli::prompt::{ask, ask_path, confirm};
 use crate::config;
 use crate::config::AppConfig;
 
@@ -11,28 +14,130 @@ pub struct Args {
     #[arg(short, long)]
     pub language: Option<String>,
 
-    /// 输入视频文件路径
+    /// 输入视频文件路径，`--batch`模式下为待处理的目录
     pub input: Option<String>,
 
     /// 输出字幕文件路径
     pub output: Option<String>,
+
+    /// 字幕格式：srt/vtt/ass/lrc/txt，不指定时根据输出文件扩展名自动判断
+    #[arg(long)]
+    pub format: Option<String>,
+
+    /// 批量模式：将`input`当作目录，处理其中所有视频文件
+    #[arg(long)]
+    pub batch: bool,
+
+    /// 批量模式下字幕的统一输出目录，不指定则写在每个视频文件旁边
+    #[arg(long)]
+    pub output_dir: Option<String>,
+
+    /// 转码的目标分辨率（如`1280x720`或`720p`），用于处理前先用ffmpeg缩放过大的视频
+    #[arg(long)]
+    pub transcode: Option<String>,
+
+    /// 传给ffmpeg转码的内存限制（如"8G"）
+    #[arg(long, default_value = "8G")]
+    pub mem_limit: String,
+
+    /// 标准输入是终端且识别语言为`auto`时，额外交互式询问具体语言
+    #[arg(long)]
+    pub interactive: bool,
+
+    /// 显式指定基础配置文件路径，不存在时会报错（不指定则使用默认路径，缺失时自动创建）
+    #[arg(long)]
+    pub config_file: Option<String>,
+
+    /// 命令行配置覆盖项，形如`KEY.PATH=VALUE`，可重复指定，按顺序叠加在配置文件之后
+    #[arg(long = "config", value_name = "KEY=VALUE")]
+    pub config: Vec<String>,
+
+    /// 自定义ASS字幕`[V4+ Styles]`节的样式行，覆盖配置文件中的`base.ass_style`（仅ASS格式下生效）
+    #[arg(long)]
+    pub ass_style: Option<String>,
 }
 
 /// 从命令行参数创建应用配置
+///
+/// 当缺少`input`/`output`且标准输入是终端时，会改为交互式询问而不是直接报错；
+/// 输出路径默认取`<input-stem>.srt`，需要用户确认或手动输入其他路径。
+/// `--interactive`不影响这一步，它只额外控制识别语言的交互式询问。
 pub fn create_app_config(args: Args) -> Result<AppConfig> {
-    let input = args.input.context("缺少输入视频文件路径")?;
-    let output = args.output.context("缺少输出字幕文件路径")?;
+    let is_tty = std::io::stdin().is_terminal();
 
-    // 加载配置文件
-    let config = config::load_config()?;
+    let input = match args.input {
+        Some(input) => input,
+        None if is_tty => ask_path("输入视频文件路径 > ")?,
+        None => anyhow::bail!("缺少输入视频文件路径"),
+    };
+
+    // 加载分层配置：基础config.toml -> config.d/*.toml -> --config覆盖项
+    let config = config::load_layered_config(args.config_file.as_deref().map(Path::new), &args.config)?;
+
+    let mut output = match args.output {
+        Some(output) => output,
+        None if is_tty => {
+            let default_output = default_output_path(&input);
+            if confirm(&format!("输出字幕文件路径默认为 {}，是否使用？", default_output), true)? {
+                default_output
+            } else {
+                ask("输出字幕文件路径 > ")?
+            }
+        }
+        None => anyhow::bail!("缺少输出字幕文件路径"),
+    };
+
+    // `--format`优先于配置文件的`format`，都未指定时保留输出路径原有的扩展名
+    if let Some(format) = args.format.or_else(|| config.base.format.clone()) {
+        output = apply_format_extension(&output, &format);
+    }
+
+    let ass_style = args.ass_style.or_else(|| config.base.ass_style.clone());
+
+    let mut language = args.language.unwrap_or(config.base.language);
+    if args.interactive && is_tty && language == "auto" {
+        let answer = ask("识别语言 (auto/zh/ja/... 直接回车使用auto) > ")?;
+        if !answer.is_empty() {
+            language = answer;
+        }
+    }
 
     // 创建应用配置
     let app_config = AppConfig {
         input,
         output,
         model: config.base.model,
-        language: args.language.unwrap_or(config.base.language),
+        language,
+        ass_style,
+        translate: config.translate,
+        model_source: config.model.source,
     };
 
     Ok(app_config)
 }
+
+/// 根据输入文件路径推导默认的输出字幕路径：`<input-stem>.srt`
+fn default_output_path(input: &str) -> String {
+    let stem = Path::new(input)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "output".to_string());
+
+    format!("{}.srt", stem)
+}
+
+/// 把输出路径的扩展名替换为`format`对应的扩展名（srt/vtt/ass/lrc/txt），未识别的格式名回退为srt
+pub(crate) fn apply_format_extension(output: &str, format: &str) -> String {
+    let ext = match format.to_lowercase().as_str() {
+        "vtt" => "vtt",
+        "ass" | "ssa" => "ass",
+        "lrc" => "lrc",
+        "txt" => "txt",
+        _ => "srt",
+    };
+
+    Path::new(output)
+        .with_extension(ext)
+        .to_string_lossy()
+        .to_string()
+}
@@ -0,0 +1,213 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cli::args::apply_format_extension;
+use crate::config::{FilesConfig, ModelSourceConfig, TranslateConfig};
+use crate::media::{transcode_video, Resolution};
+use crate::whisper::transcribe_audio;
+
+/// 支持批量处理的视频扩展名
+const VIDEO_EXTENSIONS: [&str; 4] = ["mp4", "avi", "mov", "mkv"];
+
+/// 批量处理一个目录下的所有视频文件，逐个生成字幕
+///
+/// 输出默认写到每个视频同目录下的`<stem>.srt`，也可通过`output_dir`统一收集到一处。
+/// 若提供了`transcode`，会先用ffmpeg把视频缩放到目标分辨率，再进行音频提取与转录。
+/// 若提供了`format`，每个输出路径的扩展名会替换为对应格式，否则保持`.srt`。
+pub fn run_batch(
+    input_dir: &Path,
+    output_dir: Option<&Path>,
+    model: &str,
+    language: &str,
+    transcode: Option<(Resolution, &str)>,
+    format: Option<&str>,
+    ass_style: Option<&str>,
+    translate_config: &TranslateConfig,
+    model_source: Option<&ModelSourceConfig>,
+) -> Result<()> {
+    let mut videos: Vec<PathBuf> = fs::read_dir(input_dir)
+        .context("无法读取输入目录")?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .map(|ext| VIDEO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+                .unwrap_or(false)
+        })
+        .collect();
+    videos.sort();
+
+    let total = videos.len();
+    println!("共找到 {} 个待处理的视频文件", total);
+
+    if let Some(dir) = output_dir {
+        fs::create_dir_all(dir).context("无法创建输出目录")?;
+    }
+
+    for (index, video_path) in videos.iter().enumerate() {
+        println!("[{}/{}] 处理: {:?}", index + 1, total, video_path);
+
+        let stem = video_path.file_stem().unwrap_or_default().to_string_lossy();
+        let output_path = match output_dir {
+            Some(dir) => dir.join(format!("{}.srt", stem)),
+            None => video_path.with_extension("srt"),
+        };
+        let output_path = match format {
+            Some(format) => {
+                PathBuf::from(apply_format_extension(&output_path.to_string_lossy(), format))
+            }
+            None => output_path,
+        };
+
+        let transcoded_path = if let Some((resolution, mem_limit)) = transcode {
+            let transcoded_path = std::env::temp_dir().join(format!("{}_transcoded.mp4", stem));
+            if let Err(e) = transcode_video(video_path, &transcoded_path, resolution, mem_limit) {
+                eprintln!("转码 {:?} 失败，将跳过该文件: {}", video_path, e);
+                continue;
+            }
+            Some(transcoded_path)
+        } else {
+            None
+        };
+        let transcribe_input = transcoded_path.as_deref().unwrap_or(video_path);
+
+        if let Err(e) = transcribe_audio(
+            transcribe_input.to_str().unwrap(),
+            output_path.to_str().unwrap(),
+            model,
+            language,
+            ass_style,
+            translate_config,
+            model_source,
+        ) {
+            eprintln!("处理 {:?} 失败: {}", video_path, e);
+        }
+
+        // 清理转码产生的临时视频文件
+        if let Some(transcoded_path) = transcoded_path {
+            let _ = fs::remove_file(&transcoded_path);
+        }
+    }
+
+    println!("批量处理完成");
+    Ok(())
+}
+
+/// 按`[files]`配置驱动的无人值守批量转录
+///
+/// 递归遍历`input_path`，按`include`扩展名过滤视频；`keep_file_structure`为true时，
+/// `output_path`下会保持与输入相同的相对子目录结构，否则所有字幕平铺在`output_path`下。
+/// 每个文件转录成功后按`cleanup.original_cleanup_behavior`处理原始视频。
+/// 若提供了`format`，每个输出路径的扩展名会替换为对应格式，否则保持`.srt`。
+pub fn run_batch_from_config(
+    config: &FilesConfig,
+    model: &str,
+    language: &str,
+    format: Option<&str>,
+    ass_style: Option<&str>,
+    translate_config: &TranslateConfig,
+    model_source: Option<&ModelSourceConfig>,
+) -> Result<()> {
+    let input_dir = Path::new(&config.input_path);
+    let output_dir = Path::new(&config.output_path);
+    fs::create_dir_all(output_dir).context("无法创建输出目录")?;
+
+    let mut videos = Vec::new();
+    collect_videos(input_dir, &config.include, &mut videos)?;
+    videos.sort();
+
+    let total = videos.len();
+    println!("共找到 {} 个待处理的视频文件", total);
+
+    for (index, video_path) in videos.iter().enumerate() {
+        println!("[{}/{}] 处理: {:?}", index + 1, total, video_path);
+
+        let relative = video_path
+            .strip_prefix(input_dir)
+            .unwrap_or(video_path.as_path());
+        let output_path = if config.keep_file_structure {
+            let destination = output_dir.join(relative).with_extension("srt");
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent).context("无法创建输出子目录")?;
+            }
+            destination
+        } else {
+            let stem = video_path.file_stem().unwrap_or_default().to_string_lossy();
+            output_dir.join(format!("{}.srt", stem))
+        };
+        let output_path = match format {
+            Some(format) => {
+                PathBuf::from(apply_format_extension(&output_path.to_string_lossy(), format))
+            }
+            None => output_path,
+        };
+
+        if let Err(e) = transcribe_audio(
+            video_path.to_str().unwrap(),
+            output_path.to_str().unwrap(),
+            model,
+            language,
+            ass_style,
+            translate_config,
+            model_source,
+        ) {
+            eprintln!("处理 {:?} 失败: {}", video_path, e);
+            continue;
+        }
+
+        cleanup_original(video_path, input_dir, config)?;
+    }
+
+    println!("批量处理完成");
+    Ok(())
+}
+
+/// 递归收集`dir`下扩展名属于`include`的视频文件
+fn collect_videos(dir: &Path, include: &[String], videos: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir).context("无法读取输入目录")? {
+        let path = entry.context("读取目录项失败")?.path();
+        if path.is_dir() {
+            collect_videos(&path, include, videos)?;
+            continue;
+        }
+
+        let matches = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| include.iter().any(|wanted| wanted.eq_ignore_ascii_case(ext)))
+            .unwrap_or(false);
+        if matches {
+            videos.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 按配置的清理策略处理已成功转录的原始视频文件
+fn cleanup_original(video_path: &Path, input_dir: &Path, config: &FilesConfig) -> Result<()> {
+    match config.cleanup.original_cleanup_behavior.as_str() {
+        "delete" => {
+            fs::remove_file(video_path).context("删除原始视频失败")?;
+        }
+        "archive" => {
+            let archive_root = Path::new(&config.cleanup.archive_path);
+            let relative = video_path.strip_prefix(input_dir).unwrap_or(video_path);
+            let destination = archive_root.join(relative);
+            if let Some(parent) = destination.parent() {
+                fs::create_dir_all(parent).context("无法创建归档目录")?;
+            }
+            fs::rename(video_path, &destination).context("归档原始视频失败")?;
+        }
+        _ => return Ok(()),
+    }
+
+    if config.cleanup.remove_empty_dirs {
+        if let Some(parent) = video_path.parent() {
+            let _ = fs::remove_dir(parent);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod args;
+pub mod batch;
+pub mod prompt;
+
+pub use args::Args;
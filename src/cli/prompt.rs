@@ -0,0 +1,39 @@
+use anyhow::{Context, Result};
+use std::io::{self, Write};
+use std::path::Path;
+
+/// 显示提示并读取一行用户输入，自动去除首尾空白
+pub fn ask(prompt: &str) -> Result<String> {
+    print!("{}", prompt);
+    io::stdout().flush().context("无法刷新标准输出")?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input).context("读取输入失败")?;
+    Ok(input.trim().to_string())
+}
+
+/// 循环提示，直到用户输入一个存在的文件路径
+pub fn ask_path(prompt: &str) -> Result<String> {
+    loop {
+        let path = ask(prompt)?;
+        if path.is_empty() {
+            println!("路径不能为空，请重新输入");
+            continue;
+        }
+        if !Path::new(&path).exists() {
+            println!("文件不存在: {}，请重新输入", path);
+            continue;
+        }
+        return Ok(path);
+    }
+}
+
+/// 询问一个是/否确认，直接回车则采用`default_yes`
+pub fn confirm(prompt: &str, default_yes: bool) -> Result<bool> {
+    let hint = if default_yes { "[Y/n]" } else { "[y/N]" };
+    let answer = ask(&format!("{} {} ", prompt, hint))?;
+    if answer.is_empty() {
+        return Ok(default_yes);
+    }
+    Ok(matches!(answer.to_lowercase().as_str(), "y" | "yes"))
+}
@@ -2,7 +2,8 @@ use anyhow::{Context, Result};
 use directories::ProjectDirs;
 use serde::Deserialize;
 use std::fs;
-use std::io::{Read, Write};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 // 默认模型名称
 pub const DEFAULT_MODEL: &str = "ggml-medium-q8_0.bin";
@@ -11,11 +12,105 @@ pub const DEFAULT_MODEL: &str = "ggml-medium-q8_0.bin";
 pub struct BaseConfig {
     pub model: String,
     pub language: String,
+    /// 默认字幕格式：srt/vtt/ass/lrc/txt，不指定时按输出文件扩展名推断
+    #[serde(default)]
+    pub format: Option<String>,
+    /// 自定义ASS字幕`[V4+ Styles]`节的样式行模板，不指定则使用内置默认样式
+    #[serde(default)]
+    pub ass_style: Option<String>,
+}
+
+/// 字幕翻译配置
+#[derive(Debug, Clone, Deserialize)]
+pub struct TranslateConfig {
+    /// 是否启用翻译
+    pub enabled: bool,
+    /// 翻译后端：google / deepl / deeplx / openai
+    pub backend: String,
+    /// 翻译接口的API Key（Google免key接口可留空）
+    pub api_key: String,
+    /// 翻译接口地址，留空则使用后端的默认地址（仅google/openai有默认地址，deepl/deeplx必须填写）
+    pub api_base: String,
+    /// 目标语言
+    pub target_language: String,
+    /// 是否输出原文+译文的双语字幕，为false则只保留译文
+    pub bilingual: bool,
+}
+
+/// 批量处理原始视频文件的清理策略
+#[derive(Debug, Deserialize)]
+pub struct CleanupConfig {
+    /// 清理行为："delete" | "archive" | "keep"
+    pub original_cleanup_behavior: String,
+    /// `original_cleanup_behavior = "archive"`时的归档目录
+    #[serde(default)]
+    pub archive_path: String,
+    /// 归档/删除后是否顺带清理产生的空目录
+    #[serde(default)]
+    pub remove_empty_dirs: bool,
+}
+
+/// 模型来源：可以是一条直接的下载地址，也可以是Git风格的`{ url, revision }`
+#[derive(Debug, Deserialize, Clone)]
+pub struct ModelSourceConfig {
+    /// 下载地址；指定了`revision`时视为仓库基地址，拼接为`{url}/resolve/{revision}/<model_name>`
+    pub url: String,
+    /// Git风格的版本/分支标签；留空时，`url`以模型/归档文件扩展名结尾则直接下载，否则视为基地址并拼接`<model_name>`
+    #[serde(default)]
+    pub revision: Option<String>,
+    /// 主地址失败时依次尝试的镜像地址（与`revision`无关，均视为直接下载地址）
+    #[serde(default)]
+    pub mirrors: Vec<String>,
+    /// 期望的文件SHA-256，用于校验下载内容；不匹配时会尝试下一个镜像重新下载
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+/// 模型相关配置
+#[derive(Debug, Deserialize, Default)]
+pub struct ModelConfig {
+    /// 自定义模型来源，不存在`[model.source]`小节时为`None`，退回内置的默认下载地址
+    #[serde(default)]
+    pub source: Option<ModelSourceConfig>,
+}
+
+/// 批量处理涉及的文件路径与清理策略
+#[derive(Debug, Deserialize)]
+pub struct FilesConfig {
+    /// 待处理视频所在目录
+    pub input_path: String,
+    /// 字幕输出目录
+    pub output_path: String,
+    /// 参与处理的视频扩展名（不含`.`）
+    pub include: Vec<String>,
+    /// 是否在输出目录下保持与输入目录相同的相对子目录结构
+    #[serde(default)]
+    pub keep_file_structure: bool,
+    #[serde(default = "CleanupConfig::default")]
+    pub cleanup: CleanupConfig,
+}
+
+impl Default for CleanupConfig {
+    fn default() -> Self {
+        Self {
+            original_cleanup_behavior: "keep".to_string(),
+            archive_path: String::new(),
+            remove_empty_dirs: false,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct Config {
     pub base: BaseConfig,
+    #[serde(default = "TranslateConfig::default")]
+    pub translate: TranslateConfig,
+    /// 批量处理配置，不存在`[files]`小节时为`None`
+    #[serde(default)]
+    pub files: Option<FilesConfig>,
+    /// 模型来源配置
+    #[serde(default)]
+    pub model: ModelConfig,
 }
 
 impl Default for BaseConfig {
@@ -23,6 +118,21 @@ impl Default for BaseConfig {
         Self {
             model: DEFAULT_MODEL.to_string(),
             language: "auto".to_string(),
+            format: None,
+            ass_style: None,
+        }
+    }
+}
+
+impl Default for TranslateConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            backend: "google".to_string(),
+            api_key: String::new(),
+            api_base: String::new(),
+            target_language: "en".to_string(),
+            bilingual: true,
         }
     }
 }
@@ -31,6 +141,9 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             base: BaseConfig::default(),
+            translate: TranslateConfig::default(),
+            files: None,
+            model: ModelConfig::default(),
         }
     }
 }
@@ -47,24 +160,52 @@ impl Config {
     }
 }
 
-/// 加载配置文件
-pub fn load_config() -> Result<Config> {
-    // 获取配置文件路径
-    let proj_dirs = ProjectDirs::from("", "", "subtitle-generator")
-        .context("无法确定用户配置目录")?;
-    
-    let config_dir = proj_dirs.config_dir();
+/// 合并了命令行参数与配置文件的运行时应用配置
+pub struct AppConfig {
+    pub input: String,
+    pub output: String,
+    pub model: String,
+    pub language: String,
+    /// 自定义ASS字幕样式模板，命令行`--ass-style`优先于配置文件`base.ass_style`
+    pub ass_style: Option<String>,
+    /// 字幕翻译配置，来自分层配置文件与命令行`--config`覆盖项合并后的结果
+    pub translate: TranslateConfig,
+    /// 自定义模型来源，不存在`[model.source]`小节时为`None`，退回内置的默认下载地址
+    pub model_source: Option<ModelSourceConfig>,
+}
+
+/// 一个待合并的配置文件来源
+///
+/// `required = true`的来源缺失时会报错；`required = false`（如`config.d`下的文件）缺失或读取
+/// 失败时只在标准错误输出一条警告后跳过，不影响其余来源的合并。
+#[derive(Debug, Clone)]
+pub struct ConfigSource {
+    pub path: PathBuf,
+    pub required: bool,
+}
+
+/// 默认配置目录下的用户级配置文件所在目录
+fn default_config_dir() -> Result<PathBuf> {
+    let proj_dirs =
+        ProjectDirs::from("", "", "subtitle-generator").context("无法确定用户配置目录")?;
+
+    let config_dir = proj_dirs.config_dir().to_path_buf();
     if !config_dir.exists() {
-        fs::create_dir_all(config_dir).context("无法创建配置目录")?;
+        fs::create_dir_all(&config_dir).context("无法创建配置目录")?;
     }
-    
-    let config_path = config_dir.join("config.toml");
-    
-    // 如果配置文件不存在，创建默认配置文件
-    if !config_path.exists() {
-        println!("配置文件不存在，正在创建默认配置文件...");
-        
-        let default_config = format!(r#"# 字幕生成器配置文件 - 自动生成
+    Ok(config_dir)
+}
+
+/// 若基础配置文件不存在则写入一份默认配置
+fn ensure_default_config_file(config_path: &Path) -> Result<()> {
+    if config_path.exists() {
+        return Ok(());
+    }
+
+    println!("配置文件不存在，正在创建默认配置文件...");
+
+    let default_config = format!(
+        r#"# 字幕生成器配置文件 - 自动生成
 
 [base]
 # Whisper模型名称
@@ -72,17 +213,186 @@ model = "{}"
 
 # 识别语言 (例如: zh, ja, auto)
 language = "auto"
-"#, DEFAULT_MODEL);
 
-        let mut file = fs::File::create(&config_path).context(format!("无法创建配置文件: {:?}", config_path))?;
-        file.write_all(default_config.as_bytes()).context("无法写入默认配置")?;
-        println!("已创建默认配置文件: {:?}", config_path);
+# 默认字幕格式：srt/vtt/ass/lrc/txt，留空则按输出文件扩展名推断
+# format = "srt"
+
+# 自定义ASS字幕[V4+ Styles]节的样式行，留空则使用内置默认样式
+# ass_style = "Style: Default,Arial,20,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,0,2,10,10,10,1"
+
+[translate]
+# 是否启用字幕翻译
+enabled = false
+# 翻译后端：google / deepl / deeplx / openai
+backend = "google"
+# 翻译接口的API Key（Google免key接口可留空）
+api_key = ""
+# 翻译接口地址，留空则使用后端的默认地址（仅google/openai有默认地址，deepl/deeplx必须填写）
+api_base = ""
+# 目标语言
+target_language = "en"
+# 是否输出原文+译文的双语字幕，为false则只保留译文
+bilingual = true
+
+# [files]
+# 批量处理的输入/输出目录，取消注释以启用无人值守批量转录
+# input_path = "./videos"
+# output_path = "./subtitles"
+# include = ["mp4", "mkv", "avi"]
+# keep_file_structure = true
+#
+# [files.cleanup]
+# original_cleanup_behavior = "keep"
+# archive_path = "./videos/processed"
+# remove_empty_dirs = false
+
+# [model.source]
+# 自定义模型下载来源，取消注释以替换内置的huggingface地址
+# url = "https://huggingface.co/ggerganov/whisper.cpp"
+# revision = "main"
+# mirrors = ["https://mirror.example.com/whisper.cpp/resolve/main/ggml-medium-q8_0.bin"]
+# sha256 = ""
+"#,
+        DEFAULT_MODEL
+    );
+
+    let mut file = fs::File::create(config_path)
+        .context(format!("无法创建配置文件: {:?}", config_path))?;
+    file.write_all(default_config.as_bytes())
+        .context("无法写入默认配置")?;
+    println!("已创建默认配置文件: {:?}", config_path);
+    Ok(())
+}
+
+/// 解析分层配置涉及的所有文件路径，按合并顺序排列：
+/// 基础`config.toml`（或`explicit_base`指定的文件）-> `config.d/`目录下按文件名排序的`*.toml`。
+///
+/// 返回的列表可供未来的配置热更新（watch模式）观察这些文件；命令行的`--config KEY=VALUE`
+/// 覆盖项不在此列表中，它们在合并后直接作用于内存中的配置，没有对应的文件。
+pub fn resolve_config_paths(explicit_base: Option<&Path>) -> Result<Vec<ConfigSource>> {
+    let config_dir = default_config_dir()?;
+
+    let base_path = match explicit_base {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let default_path = config_dir.join("config.toml");
+            ensure_default_config_file(&default_path)?;
+            default_path
+        }
+    };
+
+    let mut sources = vec![ConfigSource {
+        path: base_path,
+        required: true,
+    }];
+
+    let config_d = config_dir.join("config.d");
+    if config_d.is_dir() {
+        let mut extra: Vec<PathBuf> = fs::read_dir(&config_d)
+            .context("无法读取config.d目录")?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("toml"))
+            .collect();
+        extra.sort();
+        sources.extend(
+            extra
+                .into_iter()
+                .map(|path| ConfigSource { path, required: false }),
+        );
+    }
+
+    Ok(sources)
+}
+
+/// 将`overlay`递归合并进`base`，标量与数组由`overlay`直接覆盖，表则逐键合并
+fn merge_toml(base: &mut toml::Value, overlay: toml::Value) {
+    match overlay {
+        toml::Value::Table(overlay_table) => {
+            if let toml::Value::Table(base_table) = base {
+                for (key, value) in overlay_table {
+                    match base_table.get_mut(&key) {
+                        Some(existing) => merge_toml(existing, value),
+                        None => {
+                            base_table.insert(key, value);
+                        }
+                    }
+                }
+            } else {
+                *base = toml::Value::Table(overlay_table);
+            }
+        }
+        other => *base = other,
+    }
+}
+
+/// 将一个`--config`覆盖值解析为布尔/整数/浮点数，否则作为字符串
+fn parse_override_value(raw: &str) -> toml::Value {
+    if let Ok(b) = raw.parse::<bool>() {
+        toml::Value::Boolean(b)
+    } else if let Ok(i) = raw.parse::<i64>() {
+        toml::Value::Integer(i)
+    } else if let Ok(f) = raw.parse::<f64>() {
+        toml::Value::Float(f)
+    } else {
+        toml::Value::String(raw.to_string())
     }
-    
-    // 读取并解析配置文件
-    let mut file = fs::File::open(&config_path).context(format!("无法打开配置文件: {:?}", config_path))?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents).context("无法读取配置文件内容")?;
-    let config: Config = toml::from_str(&contents).context("无法解析TOML配置文件")?;
-    Ok(config)
+}
+
+/// 按点号分隔的路径（如`translate.enabled`）写入一个值，缺失的中间表会自动创建
+fn set_by_path(root: &mut toml::Value, key_path: &str, value: toml::Value) {
+    let mut current = root;
+    let mut segments = key_path.split('.').peekable();
+    while let Some(segment) = segments.next() {
+        if !current.is_table() {
+            *current = toml::Value::Table(toml::value::Table::new());
+        }
+        let table = current.as_table_mut().expect("刚确保过是表");
+
+        if segments.peek().is_none() {
+            table.insert(segment.to_string(), value);
+            return;
+        }
+
+        current = table
+            .entry(segment.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::value::Table::new()));
+    }
+}
+
+/// 加载分层配置：基础`config.toml`（或`explicit_base`）-> `config.d/*.toml`（按文件名排序）
+/// -> `overrides`中形如`key.path=value`的命令行覆盖项，按顺序依次叠加
+pub fn load_layered_config(explicit_base: Option<&Path>, overrides: &[String]) -> Result<Config> {
+    let sources = resolve_config_paths(explicit_base)?;
+
+    let mut merged = toml::Value::Table(toml::value::Table::new());
+    for source in &sources {
+        let contents = match fs::read_to_string(&source.path) {
+            Ok(contents) => contents,
+            Err(e) if !source.required => {
+                eprintln!("跳过无法读取的可选配置文件 {:?}: {}", source.path, e);
+                continue;
+            }
+            Err(e) => return Err(e).context(format!("无法读取配置文件: {:?}", source.path)),
+        };
+
+        let value: toml::Value = contents
+            .parse()
+            .context(format!("无法解析TOML配置文件: {:?}", source.path))?;
+        merge_toml(&mut merged, value);
+    }
+
+    for raw in overrides {
+        let (key_path, raw_value) = raw
+            .split_once('=')
+            .with_context(|| format!("无效的--config覆盖项，应为KEY=VALUE形式: {}", raw))?;
+        set_by_path(&mut merged, key_path, parse_override_value(raw_value));
+    }
+
+    merged.try_into().context("无法解析合并后的配置")
+}
+
+/// 加载配置文件（不带`config.d`以外的来源、不带命令行覆盖项）
+pub fn load_config() -> Result<Config> {
+    load_layered_config(None, &[])
 }
@@ -1,19 +1,25 @@
-use anyhow::Result;
 use eframe::{egui, CreationContext};
 use egui::{CentralPanel, Color32, RichText, Rounding, TopBottomPanel, Ui};
 use rfd::FileDialog;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use std::time::Instant;
 
-use crate::config::{load_config, AppConfig};
-use crate::whisper::transcribe_audio;
+use crate::config::{load_config, ModelSourceConfig, TranslateConfig};
+use crate::record::Recorder;
+use crate::whisper::{transcribe_audio_with_progress, transcribe_samples};
+
+/// 支持拖拽导入的视频扩展名
+const VIDEO_EXTENSIONS: [&str; 4] = ["mp4", "avi", "mov", "mkv"];
 
 /// 应用状态
 #[derive(Clone, PartialEq)]
 enum AppStatus {
     Initial,       // 初始状态，等待选择文件
     FileSelected,  // 已选择文件，等待处理
+    Recording,     // 正在录音
     Processing,    // 处理中
     Completed,     // 完成，等待保存
     SaveSuccess,   // 保存成功
@@ -26,6 +32,7 @@ struct ProgressInfo {
     message: String,
     progress: f32,
     subtitle_content: Option<String>, // 保存生成的字幕内容
+    preview: String,                  // 转录过程中逐段追加的文本预览
 }
 
 impl Default for ProgressInfo {
@@ -35,6 +42,7 @@ impl Default for ProgressInfo {
             message: "请选择视频文件".to_string(),
             progress: 0.0,
             subtitle_content: None,
+            preview: String::new(),
         }
     }
 }
@@ -45,7 +53,12 @@ pub struct VideoSubtitleApp {
     output_path: Option<String>,
     language: String,
     model: String,
+    ass_style: Option<String>,
+    translate: TranslateConfig,
+    model_source: Option<ModelSourceConfig>,
     progress_info: Arc<Mutex<ProgressInfo>>,
+    abort_flag: Arc<AtomicBool>,
+    recorder: Option<(Recorder, Instant)>,
 }
 
 impl Default for VideoSubtitleApp {
@@ -53,7 +66,7 @@ impl Default for VideoSubtitleApp {
         // 加载默认配置
         let config = load_config().unwrap_or_else(|_| {
             eprintln!("无法加载配置，使用默认值");
-            crate::config::ConfigFile::default()
+            crate::config::Config::default()
         });
 
         Self {
@@ -61,7 +74,12 @@ impl Default for VideoSubtitleApp {
             output_path: None,
             language: config.base.language,
             model: config.base.model,
+            ass_style: config.base.ass_style,
+            translate: config.translate,
+            model_source: config.model.source,
             progress_info: Arc::new(Mutex::new(ProgressInfo::default())),
+            abort_flag: Arc::new(AtomicBool::new(false)),
+            recorder: None,
         }
     }
 }
@@ -88,16 +106,11 @@ impl VideoSubtitleApp {
     /// 选择输入文件
     fn select_input_file(&mut self) {
         if let Some(path) = FileDialog::new()
-            .add_filter("视频文件", &["mp4", "avi", "mov", "mkv"])
+            .add_filter("视频文件", &VIDEO_EXTENSIONS)
             .set_title("选择视频文件")
             .pick_file()
         {
-            self.input_path = Some(path.to_string_lossy().to_string());
-
-            // 更新状态为已选择文件
-            let mut info = self.progress_info.lock().unwrap();
-            info.status = AppStatus::FileSelected;
-            info.message = "文件已选择，请点击生成字幕".to_string();
+            self.accept_input_path(path.to_string_lossy().to_string());
         } else {
             // 如果取消选择，则回到初始状态
             if self.progress_info.lock().unwrap().status == AppStatus::FileSelected {
@@ -106,6 +119,185 @@ impl VideoSubtitleApp {
         }
     }
 
+    /// 采用一个输入文件路径（来自文件对话框或拖拽），校验扩展名后跳转到"已选择文件"状态
+    fn accept_input_path(&mut self, path: String) {
+        let is_video = Path::new(&path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| VIDEO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+            .unwrap_or(false);
+
+        if !is_video {
+            let mut info = self.progress_info.lock().unwrap();
+            info.status = AppStatus::Error("仅支持 mp4/avi/mov/mkv 格式的视频文件".to_string());
+            return;
+        }
+
+        self.input_path = Some(path);
+
+        let mut info = self.progress_info.lock().unwrap();
+        info.status = AppStatus::FileSelected;
+        info.message = "文件已选择，请点击生成字幕".to_string();
+    }
+
+    /// 开始麦克风录音
+    fn start_recording(&mut self) {
+        match Recorder::start() {
+            Ok(recorder) => {
+                self.recorder = Some((recorder, Instant::now()));
+
+                let mut info = self.progress_info.lock().unwrap();
+                info.status = AppStatus::Recording;
+                info.message = "正在录音...".to_string();
+            }
+            Err(e) => {
+                let mut info = self.progress_info.lock().unwrap();
+                info.status = AppStatus::Error(format!("无法开始录音: {}", e));
+            }
+        }
+    }
+
+    /// 停止录音并直接对采集到的音频进行转录
+    fn stop_recording(&mut self) {
+        let Some((recorder, _)) = self.recorder.take() else {
+            return;
+        };
+
+        let samples = recorder.stop();
+        let model = self.model.clone();
+        let language = self.language.clone();
+        let ass_style = self.ass_style.clone();
+        let translate_config = self.translate.clone();
+        let model_source = self.model_source.clone();
+        let temp_output_path = std::env::temp_dir()
+            .join("recording.temp.srt")
+            .to_string_lossy()
+            .to_string();
+
+        let progress_info = self.progress_info.clone();
+        let abort_flag = Arc::new(AtomicBool::new(false));
+        self.abort_flag = abort_flag.clone();
+
+        {
+            let mut info = progress_info.lock().unwrap();
+            info.status = AppStatus::Processing;
+            info.message = "正在转录录音...".to_string();
+            info.progress = 0.0;
+            info.preview.clear();
+        }
+
+        thread::spawn(move || {
+            let model_path = match crate::model::ensure_model_exists_sync(&model, model_source.as_ref()) {
+                Ok(path) => path,
+                Err(e) => {
+                    let mut info = progress_info.lock().unwrap();
+                    info.status = AppStatus::Error(format!("无法加载模型: {}", e));
+                    return;
+                }
+            };
+
+            let on_progress = {
+                let progress_info = progress_info.clone();
+                move |progress: i32| {
+                    let mut info = progress_info.lock().unwrap();
+                    info.progress = (progress as f32 / 100.0).clamp(0.0, 1.0);
+                }
+            };
+
+            let on_segment = {
+                let progress_info = progress_info.clone();
+                move |text: &str| {
+                    let mut info = progress_info.lock().unwrap();
+                    if !info.preview.is_empty() {
+                        info.preview.push('\n');
+                    }
+                    info.preview.push_str(text);
+                }
+            };
+
+            let result = transcribe_samples(
+                &samples,
+                &temp_output_path,
+                model_path.to_string_lossy().as_ref(),
+                &language,
+                ass_style.as_deref(),
+                &translate_config,
+                abort_flag,
+                on_progress,
+                on_segment,
+            );
+
+            match result {
+                Ok(_) => match std::fs::read_to_string(&temp_output_path) {
+                    Ok(content) => {
+                        let mut info = progress_info.lock().unwrap();
+                        info.subtitle_content = Some(content);
+                        info.status = AppStatus::Completed;
+                        info.message = "字幕生成完成！".to_string();
+                        info.progress = 1.0;
+
+                        if let Err(e) = std::fs::remove_file(&temp_output_path) {
+                            eprintln!("无法删除临时文件: {}", e);
+                        }
+                    }
+                    Err(e) => {
+                        let mut info = progress_info.lock().unwrap();
+                        info.status = AppStatus::Error(format!("读取生成的字幕失败: {}", e));
+                    }
+                },
+                Err(e) => {
+                    let mut info = progress_info.lock().unwrap();
+                    info.status = AppStatus::Error(format!("错误: {}", e));
+                    info.progress = 0.0;
+                }
+            }
+        });
+    }
+
+    /// 处理拖入窗口的文件：松开后导入视频，拖拽悬停时显示提示遮罩
+    fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let dropped_path = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .first()
+                .and_then(|file| file.path.clone())
+        });
+
+        if let Some(path) = dropped_path {
+            // 处理中/录音中有后台线程或Recorder在持续写入同一份状态，此时接受新文件会被其
+            // 覆盖，因此只在空闲状态（初始/已选择/已完成/出错）下才接受拖入的文件
+            let status = self.progress_info.lock().unwrap().status.clone();
+            let can_accept_drop = matches!(
+                status,
+                AppStatus::Initial | AppStatus::FileSelected | AppStatus::Completed | AppStatus::Error(_)
+            );
+            if can_accept_drop {
+                self.accept_input_path(path.to_string_lossy().to_string());
+            }
+        }
+
+        let is_hovering = ctx.input(|i| !i.raw.hovered_files.is_empty());
+        if is_hovering {
+            egui::Area::new(egui::Id::new("file_drop_overlay"))
+                .fixed_pos(egui::pos2(0.0, 0.0))
+                .order(egui::Order::Foreground)
+                .show(ctx, |ui| {
+                    let screen_rect = ctx.screen_rect();
+                    ui.painter()
+                        .rect_filled(screen_rect, 0.0, Color32::from_black_alpha(160));
+                    ui.allocate_ui_at_rect(screen_rect, |ui| {
+                        ui.centered_and_justified(|ui| {
+                            ui.label(
+                                RichText::new("松开以导入视频")
+                                    .size(24.0)
+                                    .color(Color32::WHITE),
+                            );
+                        });
+                    });
+                });
+        }
+    }
+
     /// 保存字幕文件
     fn save_subtitle_file(&mut self) {
         let content = {
@@ -161,6 +353,12 @@ impl VideoSubtitleApp {
         info.message = "请选择视频文件".to_string();
         info.progress = 0.0;
         info.subtitle_content = None;
+        info.preview.clear();
+    }
+
+    /// 取消正在进行的转录
+    fn cancel_processing(&mut self) {
+        self.abort_flag.store(true, Ordering::Relaxed);
     }
 
     /// 开始处理
@@ -176,39 +374,62 @@ impl VideoSubtitleApp {
         let temp_output_path = format!("{}.temp.srt", input_path);
         let language = self.language.clone();
         let model = self.model.clone();
+        let ass_style = self.ass_style.clone();
+        let translate_config = self.translate.clone();
+        let model_source = self.model_source.clone();
 
         let progress_info = self.progress_info.clone();
 
+        // 每次处理使用全新的中止标志，避免受上一次运行的状态影响
+        let abort_flag = Arc::new(AtomicBool::new(false));
+        self.abort_flag = abort_flag.clone();
+
         // 更新状态为处理中
         {
             let mut info = progress_info.lock().unwrap();
             info.status = AppStatus::Processing;
             info.message = "正在生成字幕...".to_string();
             info.progress = 0.0;
+            info.preview.clear();
         }
 
-        // 创建配置
-        let app_config = AppConfig {
-            input: input_path,
-            output: temp_output_path.clone(),
-            model,
-            language,
-        };
-
         // 在新线程中处理，避免阻塞UI
         thread::spawn(move || {
-            // 处理过程中的进度回调
-            let progress_callback = {
+            // 真实进度回调：whisper-rs汇报的是0~100的整数百分比
+            let on_progress = {
                 let progress_info = progress_info.clone();
-                move |msg: &str, progress: f32| {
+                move |progress: i32| {
                     let mut info = progress_info.lock().unwrap();
-                    info.message = msg.to_string();
-                    info.progress = progress;
+                    info.progress = (progress as f32 / 100.0).clamp(0.0, 1.0);
                 }
             };
 
-            // 调用核心处理功能
-            match process_with_progress(&app_config, progress_callback) {
+            // 每完成一段就追加到预览缓冲区，实现流式预览
+            let on_segment = {
+                let progress_info = progress_info.clone();
+                move |text: &str| {
+                    let mut info = progress_info.lock().unwrap();
+                    if !info.preview.is_empty() {
+                        info.preview.push('\n');
+                    }
+                    info.preview.push_str(text);
+                }
+            };
+
+            let result = transcribe_audio_with_progress(
+                &input_path,
+                &temp_output_path,
+                &model,
+                &language,
+                ass_style.as_deref(),
+                &translate_config,
+                model_source.as_ref(),
+                abort_flag.clone(),
+                on_progress,
+                on_segment,
+            );
+
+            match result {
                 Ok(_) => {
                     // 读取生成的字幕内容
                     match std::fs::read_to_string(&temp_output_path) {
@@ -232,9 +453,18 @@ impl VideoSubtitleApp {
                     }
                 }
                 Err(e) => {
-                    let mut info = progress_info.lock().unwrap();
-                    info.status = AppStatus::Error(format!("错误: {}", e));
-                    info.progress = 0.0;
+                    if abort_flag.load(Ordering::Relaxed) {
+                        // 用户主动取消，直接回到初始状态
+                        let mut info = progress_info.lock().unwrap();
+                        info.status = AppStatus::Initial;
+                        info.message = "请选择视频文件".to_string();
+                        info.progress = 0.0;
+                        info.preview.clear();
+                    } else {
+                        let mut info = progress_info.lock().unwrap();
+                        info.status = AppStatus::Error(format!("错误: {}", e));
+                        info.progress = 0.0;
+                    }
                 }
             }
         });
@@ -251,6 +481,9 @@ impl VideoSubtitleApp {
             AppStatus::FileSelected => {
                 ui.label(RichText::new("请生成字幕").size(12.0));
             }
+            AppStatus::Recording => {
+                ui.label(RichText::new("录音中...").size(12.0));
+            }
             AppStatus::Processing => {
                 ui.horizontal(|ui| {
                     ui.label(RichText::new("执行中..").size(12.0));
@@ -285,9 +518,10 @@ impl VideoSubtitleApp {
                         |ui| {
                             // 添加一些垂直空间，使内容垂直居中
                             let content_height = match status {
-                                AppStatus::Initial => 28.0,       // 单个按钮的高度
+                                AppStatus::Initial => 66.0,       // 两个按钮的高度
                                 AppStatus::FileSelected => 140.0, // 文本和两个按钮的高度
-                                AppStatus::Processing => 22.0,    // 单个文本的高度
+                                AppStatus::Recording => 80.0,     // 计时文本和停止按钮的高度
+                                AppStatus::Processing => 150.0,   // 进度文本、预览区域和取消按钮的高度
                                 AppStatus::Completed => 80.0,     // 文本和一个按钮的高度
                                 AppStatus::SaveSuccess => 80.0,   // 文本和一个按钮的高度
                                 AppStatus::Error(_) => 80.0,      // 文本和一个按钮的高度
@@ -300,10 +534,41 @@ impl VideoSubtitleApp {
 
                             match status {
                                 AppStatus::Initial => {
-                                    // 初始界面只显示"选择文件"按钮
+                                    // 初始界面显示"选择文件"和"录音"两个入口
                                     if self.create_button(ui, "选择文件").clicked() {
                                         self.select_input_file();
                                     }
+
+                                    ui.add_space(4.0);
+
+                                    if self.create_button(ui, "录音").clicked() {
+                                        self.start_recording();
+                                    }
+                                }
+
+                                AppStatus::Recording => {
+                                    // 显示录音计时和停止按钮
+                                    let elapsed = self
+                                        .recorder
+                                        .as_ref()
+                                        .map(|(recorder, _)| recorder.elapsed_secs())
+                                        .unwrap_or(0.0);
+
+                                    ui.label(
+                                        RichText::new(format!(
+                                            "● 录音中 {:02}:{:02}",
+                                            elapsed as u32 / 60,
+                                            elapsed as u32 % 60
+                                        ))
+                                        .size(14.0)
+                                        .color(Color32::RED),
+                                    );
+
+                                    ui.add_space(13.0);
+
+                                    if self.create_button(ui, "停止").clicked() {
+                                        self.stop_recording();
+                                    }
                                 }
 
                                 AppStatus::FileSelected => {
@@ -332,9 +597,27 @@ impl VideoSubtitleApp {
                                 }
 
                                 AppStatus::Processing => {
-                                    // 显示处理中的状态
-                                    let info = self.progress_info.lock().unwrap();
-                                    ui.label(RichText::new(&info.message).size(14.0));
+                                    // 显示处理中的状态、实时进度和逐段预览
+                                    let (message, progress, preview) = {
+                                        let info = self.progress_info.lock().unwrap();
+                                        (info.message.clone(), info.progress, info.preview.clone())
+                                    };
+
+                                    ui.label(RichText::new(&message).size(14.0));
+                                    ui.add_space(6.0);
+                                    ui.add(egui::ProgressBar::new(progress).show_percentage());
+                                    ui.add_space(6.0);
+
+                                    egui::ScrollArea::vertical()
+                                        .max_height(60.0)
+                                        .show(ui, |ui| {
+                                            ui.label(RichText::new(&preview).size(12.0));
+                                        });
+
+                                    ui.add_space(6.0);
+                                    if self.create_button(ui, "取消").clicked() {
+                                        self.cancel_processing();
+                                    }
                                 }
 
                                 AppStatus::Completed => {
@@ -396,6 +679,9 @@ impl eframe::App for VideoSubtitleApp {
             self.render_main_panel(ui);
         });
 
+        // 处理拖拽导入视频文件
+        self.handle_dropped_files(ctx);
+
         // 当字幕生成完成后，自动提示保存
         if self.progress_info.lock().unwrap().status == AppStatus::Completed {
             self.save_subtitle_file();
@@ -426,16 +712,3 @@ fn setup_custom_styles(ctx: &egui::Context) {
     ctx.set_fonts(fonts);
     ctx.set_style(style);
 }
-
-/// 带进度回调的处理函数封装
-fn process_with_progress<F>(config: &AppConfig, progress_callback: F) -> Result<()>
-where
-    F: Fn(&str, f32) + Send + 'static,
-{
-    // TODO: 这里应该修改transcribe_audio函数以支持进度回调
-    // 目前直接调用原函数，后续可以改进
-    progress_callback("正在处理视频...", 0.5);
-    transcribe_audio(config)?;
-    progress_callback("处理完成", 1.0);
-    Ok(())
-}
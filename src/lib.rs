@@ -1,6 +1,9 @@
 pub mod cli;
 pub mod config;
 pub mod media;
+pub mod model;
+pub mod record;
+pub mod translate;
 pub mod whisper;
 pub mod gui;
 pub mod utils;
@@ -1,16 +1,80 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 
-use subtitle_generator::cli::{Args, args::create_app_config};
+use subtitle_generator::cli::batch::{run_batch, run_batch_from_config};
+use subtitle_generator::cli::{args::create_app_config, Args};
+use subtitle_generator::config;
+use subtitle_generator::media::Resolution;
 use subtitle_generator::whisper::transcribe_audio;
 
 fn main() -> Result<()> {
     // 解析命令行参数
     let args = Args::parse();
-    
+
+    if args.batch {
+        return run_batch_mode(args);
+    }
+
     // 创建应用配置
     let app_config = create_app_config(args)?;
-    
+
     // 调用核心功能
-    transcribe_audio(&app_config)
+    transcribe_audio(
+        &app_config.input,
+        &app_config.output,
+        &app_config.model,
+        &app_config.language,
+        app_config.ass_style.as_deref(),
+        &app_config.translate,
+        app_config.model_source.as_ref(),
+    )
+}
+
+/// 批量模式：`input`是待处理的目录；若命令行未指定目录，则回退到配置文件中的`[files]`小节
+fn run_batch_mode(args: Args) -> Result<()> {
+    let config = config::load_layered_config(
+        args.config_file.as_deref().map(std::path::Path::new),
+        &args.config,
+    )?;
+
+    let ass_style = args.ass_style.clone().or_else(|| config.base.ass_style.clone());
+    let format = args.format.clone().or_else(|| config.base.format.clone());
+
+    let Some(input_dir) = args.input.clone() else {
+        let files_config = config
+            .files
+            .as_ref()
+            .context("批量模式下缺少输入目录（命令行未指定，配置文件也未配置[files]）")?;
+        return run_batch_from_config(
+            files_config,
+            &config.base.model,
+            &config.base.language,
+            format.as_deref(),
+            ass_style.as_deref(),
+            &config.translate,
+            config.model.source.as_ref(),
+        );
+    };
+
+    let model = config.base.model;
+    let language = args.language.unwrap_or(config.base.language);
+
+    let transcode = args
+        .transcode
+        .as_deref()
+        .map(Resolution::parse)
+        .transpose()?
+        .map(|resolution| (resolution, args.mem_limit.as_str()));
+
+    run_batch(
+        std::path::Path::new(&input_dir),
+        args.output_dir.as_deref().map(std::path::Path::new),
+        &model,
+        &language,
+        transcode,
+        format.as_deref(),
+        ass_style.as_deref(),
+        &config.translate,
+        config.model.source.as_ref(),
+    )
 }
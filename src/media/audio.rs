@@ -1,8 +1,14 @@
 use anyhow::{Context, Result};
 use hound::{SampleFormat, WavReader};
+use rodio::{Decoder, Source};
+use std::fs::File;
+use std::io::BufReader;
 use std::path::Path;
 use std::process::Command;
 
+/// Whisper所需的采样率
+const TARGET_SAMPLE_RATE: u32 = 16000;
+
 /// 从视频中提取音频
 pub fn extract_audio_from_video(video_path: &Path, audio_path: &Path) -> Result<()> {
     let output = Command::new("ffmpeg")
@@ -53,3 +59,52 @@ pub fn parse_wav_file(path: &Path) -> Result<Vec<i16>> {
         .filter_map(Result::ok)
         .collect())
 }
+
+/// 直接解码任意音频文件（mp3/flac/ogg/wav等），下混为单声道并重采样到16kHz
+///
+/// 不再要求ffmpeg预先转成单声道16kHz的WAV：用`rodio::Decoder`读取原始采样率与声道数，
+/// 把交错的多声道帧按通道取平均下混为单声道，再线性插值重采样到Whisper所需的16kHz。
+pub fn decode_audio_file(path: &Path) -> Result<Vec<f32>> {
+    let file = File::open(path).context("无法打开音频文件")?;
+    let decoder = Decoder::new(BufReader::new(file)).context("无法解码音频文件")?;
+
+    let src_rate = decoder.sample_rate();
+    let channels = decoder.channels() as usize;
+
+    let mono_samples: Vec<f32> = if channels <= 1 {
+        decoder.convert_samples().collect()
+    } else {
+        decoder
+            .convert_samples::<f32>()
+            .collect::<Vec<f32>>()
+            .chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect()
+    };
+
+    Ok(resample_linear(&mono_samples, src_rate, TARGET_SAMPLE_RATE))
+}
+
+/// 线性插值重采样：对每个输出采样点`i`，取源位置`pos = i * src_rate / dst_rate`，
+/// 在`floor(pos)`与其下一个采样之间按小数部分插值
+pub(crate) fn resample_linear(samples: &[f32], src_rate: u32, dst_rate: u32) -> Vec<f32> {
+    if samples.is_empty() || src_rate == dst_rate {
+        return samples.to_vec();
+    }
+
+    let src_len = samples.len();
+    let dst_len = (src_len as u64 * dst_rate as u64 / src_rate as u64) as usize;
+    let mut output = Vec::with_capacity(dst_len);
+
+    for i in 0..dst_len {
+        let pos = i as f64 * src_rate as f64 / dst_rate as f64;
+        let index = pos.floor() as usize;
+        let frac = (pos - index as f64) as f32;
+
+        let current = samples[index.min(src_len - 1)];
+        let next = samples[(index + 1).min(src_len - 1)];
+        output.push(current + (next - current) * frac);
+    }
+
+    output
+}
@@ -1,5 +1,11 @@
 mod audio;
 mod subtitle;
+mod transcode;
 
-pub use audio::{extract_audio_from_video, parse_wav_file};
-pub use subtitle::generate_srt_file;
+pub use audio::{decode_audio_file, extract_audio_from_video, parse_wav_file};
+pub(crate) use audio::resample_linear;
+pub use subtitle::{
+    extract_segment_texts, generate_srt_file, generate_subtitles_translated, write_subtitles,
+    write_subtitles_with_style, SubtitleFormat,
+};
+pub use transcode::{transcode_video, Resolution};
@@ -4,7 +4,41 @@ use std::io::Write;
 use std::path::Path;
 use whisper_rs::WhisperState;
 
-/// 格式化时间戳
+use crate::config::TranslateConfig;
+use crate::translate;
+
+/// 支持导出的字幕格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+    Ass,
+    Lrc,
+    Txt,
+}
+
+impl SubtitleFormat {
+    /// 根据扩展名解析格式，无法识别时回退为SRT
+    pub fn from_extension(ext: &str) -> Self {
+        match ext.to_lowercase().as_str() {
+            "vtt" => Self::Vtt,
+            "ass" | "ssa" => Self::Ass,
+            "lrc" => Self::Lrc,
+            "txt" => Self::Txt,
+            _ => Self::Srt,
+        }
+    }
+
+    /// 根据输出文件路径的扩展名解析格式
+    pub fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .map(Self::from_extension)
+            .unwrap_or(Self::Srt)
+    }
+}
+
+/// 格式化SRT时间戳：`HH:MM:SS,mmm`
 pub fn format_timestamp(seconds: f64) -> String {
     let hours = (seconds / 3600.0) as u32;
     let minutes = ((seconds % 3600.0) / 60.0) as u32;
@@ -14,27 +48,253 @@ pub fn format_timestamp(seconds: f64) -> String {
     format!("{:02}:{:02}:{:02},{:03}", hours, minutes, secs, millisecs)
 }
 
-/// 生成SRT格式字幕
-pub fn generate_srt_file(state: &WhisperState, output_path: &Path) -> Result<()> {
-    let mut file = File::create(output_path).context("无法创建字幕文件")?;
+/// 格式化WebVTT时间戳：`HH:MM:SS.mmm`
+fn format_timestamp_vtt(seconds: f64) -> String {
+    let hours = (seconds / 3600.0) as u32;
+    let minutes = ((seconds % 3600.0) / 60.0) as u32;
+    let secs = (seconds % 60.0) as u32;
+    let millisecs = ((seconds % 1.0) * 1000.0) as u32;
+
+    format!("{:02}:{:02}:{:02}.{:03}", hours, minutes, secs, millisecs)
+}
+
+/// 格式化ASS时间戳：`H:MM:SS.cc`（百分之一秒的厘秒精度）
+fn format_timestamp_ass(seconds: f64) -> String {
+    let hours = (seconds / 3600.0) as u32;
+    let minutes = ((seconds % 3600.0) / 60.0) as u32;
+    let secs = (seconds % 60.0) as u32;
+    let centisecs = ((seconds % 1.0) * 100.0) as u32;
+
+    format!("{}:{:02}:{:02}.{:02}", hours, minutes, secs, centisecs)
+}
 
+/// 格式化LRC时间戳：`[mm:ss.xx]`
+fn format_timestamp_lrc(seconds: f64) -> String {
+    let minutes = (seconds / 60.0) as u32;
+    let secs = seconds % 60.0;
+
+    format!("[{:02}:{:05.2}]", minutes, secs)
+}
+
+/// ASS字幕默认的`[V4+ Styles]`样式，用户可通过`write_subtitles_with_style`传入自定义模板覆盖
+const DEFAULT_ASS_STYLE: &str = "Style: Default,Arial,20,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,2,0,2,10,10,10,1";
+
+/// 一个字幕段落：文本与起止时间（单位：百分之一秒，与whisper_rs保持一致）
+struct Segment {
+    text: String,
+    start: i64,
+    end: i64,
+}
+
+/// 从Whisper的状态中提取所有段落
+fn collect_segments(state: &WhisperState) -> Result<Vec<Segment>> {
     let num_segments = state.full_n_segments().context("无法获取段落数量")?;
+    let mut segments = Vec::with_capacity(num_segments as usize);
 
     for i in 0..num_segments {
-        let segment_text = state.full_get_segment_text(i).context("无法获取段落文本")?;
+        let text = state.full_get_segment_text(i).context("无法获取段落文本")?;
+        let start = state.full_get_segment_t0(i).context("无法获取开始时间戳")?;
+        let end = state.full_get_segment_t1(i).context("无法获取结束时间戳")?;
 
-        let start_time = state.full_get_segment_t0(i).context("无法获取开始时间戳")?;
+        segments.push(Segment {
+            text: text.trim().to_string(),
+            start,
+            end,
+        });
+    }
+
+    Ok(segments)
+}
 
-        let end_time = state.full_get_segment_t1(i).context("无法获取结束时间戳")?;
+/// 提取所有段落的文本，供翻译模块使用
+pub fn extract_segment_texts(state: &WhisperState) -> Result<Vec<String>> {
+    Ok(collect_segments(state)?.into_iter().map(|s| s.text).collect())
+}
 
-        let start_formatted = format_timestamp(start_time as f64 / 100.0);
-        let end_formatted = format_timestamp(end_time as f64 / 100.0);
+/// 生成SRT格式字幕
+pub fn generate_srt_file(state: &WhisperState, output_path: &Path) -> Result<()> {
+    let segments = collect_segments(state)?;
+    write_srt(&segments, None, output_path)
+}
+
+/// 按指定格式写出字幕文件，格式也可以直接从`output_path`的扩展名推断（见`SubtitleFormat::from_path`）
+pub fn write_subtitles(state: &WhisperState, output_path: &Path, format: SubtitleFormat) -> Result<()> {
+    write_subtitles_with_style(state, output_path, format, None)
+}
+
+/// 按指定格式写出字幕文件，ASS格式可传入自定义的`[V4+ Styles]`模板
+pub fn write_subtitles_with_style(
+    state: &WhisperState,
+    output_path: &Path,
+    format: SubtitleFormat,
+    ass_style_template: Option<&str>,
+) -> Result<()> {
+    let segments = collect_segments(state)?;
+    write_segments_with_style(&segments, output_path, format, ass_style_template)
+}
+
+/// 按指定格式把已提取的段落写成字幕文件，供`write_subtitles_with_style`与翻译流程共用
+fn write_segments_with_style(
+    segments: &[Segment],
+    output_path: &Path,
+    format: SubtitleFormat,
+    ass_style_template: Option<&str>,
+) -> Result<()> {
+    match format {
+        SubtitleFormat::Srt => write_srt(segments, None, output_path),
+        SubtitleFormat::Vtt => write_vtt(segments, output_path),
+        SubtitleFormat::Ass => write_ass(segments, output_path, ass_style_template),
+        SubtitleFormat::Lrc => write_lrc(segments, output_path),
+        SubtitleFormat::Txt => write_txt(segments, output_path),
+    }
+}
+
+/// 生成字幕文件，并按需携带翻译结果（双语或仅译文），支持任意`SubtitleFormat`
+///
+/// 若`config.enabled`为false，行为与`write_subtitles_with_style`完全一致。
+/// 双语模式（`config.bilingual = true`）需要在同一条目下并排展示原文与译文，目前只有SRT格式
+/// 支持；其他格式若启用了双语翻译会直接报错，提示改用SRT输出或将`bilingual`设为false。
+pub async fn generate_subtitles_translated(
+    state: &WhisperState,
+    output_path: &Path,
+    format: SubtitleFormat,
+    config: &TranslateConfig,
+    ass_style_template: Option<&str>,
+) -> Result<()> {
+    let segments = collect_segments(state)?;
+
+    if !config.enabled {
+        return write_segments_with_style(&segments, output_path, format, ass_style_template);
+    }
+
+    if config.bilingual && format != SubtitleFormat::Srt {
+        anyhow::bail!(
+            "双语字幕（translate.bilingual = true）目前仅支持SRT格式，请改用SRT输出或将bilingual设为false"
+        );
+    }
+
+    let source_texts: Vec<String> = segments.iter().map(|s| s.text.clone()).collect();
+    let translator = translate::create_translator(config)?;
+    let translated = translate::translate_segments(
+        translator.as_ref(),
+        &source_texts,
+        "auto",
+        &config.target_language,
+        |progress| println!("翻译进度: {:.0}%", progress * 100.0),
+    )
+    .await?;
+
+    if config.bilingual {
+        write_srt(&segments, Some(&translated), output_path)
+    } else {
+        let translated_segments: Vec<Segment> = segments
+            .into_iter()
+            .zip(translated.into_iter())
+            .map(|(seg, text)| Segment { text, ..seg })
+            .collect();
+        write_segments_with_style(&translated_segments, output_path, format, ass_style_template)
+    }
+}
+
+/// 将段落写成SRT文件，若提供了`translated`则在每条原文下方追加对应译文，形成双语字幕
+fn write_srt(segments: &[Segment], translated: Option<&[String]>, output_path: &Path) -> Result<()> {
+    let mut file = File::create(output_path).context("无法创建字幕文件")?;
+
+    for (i, segment) in segments.iter().enumerate() {
+        let start_formatted = format_timestamp(segment.start as f64 / 100.0);
+        let end_formatted = format_timestamp(segment.end as f64 / 100.0);
 
         writeln!(file, "{}", i + 1)?;
         writeln!(file, "{} --> {}", start_formatted, end_formatted)?;
-        writeln!(file, "{}", segment_text.trim())?;
+        writeln!(file, "{}", segment.text)?;
+
+        if let Some(translated) = translated {
+            if let Some(line) = translated.get(i) {
+                writeln!(file, "{}", line.trim())?;
+            }
+        }
+
         writeln!(file)?;
     }
 
     Ok(())
 }
+
+/// 写出WebVTT字幕（`.vtt`）
+fn write_vtt(segments: &[Segment], output_path: &Path) -> Result<()> {
+    let mut file = File::create(output_path).context("无法创建字幕文件")?;
+
+    writeln!(file, "WEBVTT")?;
+    writeln!(file)?;
+
+    for segment in segments {
+        let start_formatted = format_timestamp_vtt(segment.start as f64 / 100.0);
+        let end_formatted = format_timestamp_vtt(segment.end as f64 / 100.0);
+
+        writeln!(file, "{} --> {}", start_formatted, end_formatted)?;
+        writeln!(file, "{}", segment.text)?;
+        writeln!(file)?;
+    }
+
+    Ok(())
+}
+
+/// 写出ASS/SSA字幕（`.ass`），若未提供样式模板则使用内置的默认样式
+fn write_ass(segments: &[Segment], output_path: &Path, style_template: Option<&str>) -> Result<()> {
+    let mut file = File::create(output_path).context("无法创建字幕文件")?;
+    let style = style_template.unwrap_or(DEFAULT_ASS_STYLE);
+
+    writeln!(file, "[Script Info]")?;
+    writeln!(file, "ScriptType: v4.00+")?;
+    writeln!(file, "Collisions: Normal")?;
+    writeln!(file)?;
+    writeln!(file, "[V4+ Styles]")?;
+    writeln!(
+        file,
+        "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding"
+    )?;
+    writeln!(file, "{}", style)?;
+    writeln!(file)?;
+    writeln!(file, "[Events]")?;
+    writeln!(
+        file,
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text"
+    )?;
+
+    for segment in segments {
+        let start_formatted = format_timestamp_ass(segment.start as f64 / 100.0);
+        let end_formatted = format_timestamp_ass(segment.end as f64 / 100.0);
+        let text = segment.text.replace('\n', "\\N");
+
+        writeln!(
+            file,
+            "Dialogue: 0,{},{},Default,,0,0,0,,{}",
+            start_formatted, end_formatted, text
+        )?;
+    }
+
+    Ok(())
+}
+
+/// 写出LRC歌词字幕（`.lrc`），适合歌词/卡拉OK类播放器
+fn write_lrc(segments: &[Segment], output_path: &Path) -> Result<()> {
+    let mut file = File::create(output_path).context("无法创建字幕文件")?;
+
+    for segment in segments {
+        let tag = format_timestamp_lrc(segment.start as f64 / 100.0);
+        writeln!(file, "{}{}", tag, segment.text)?;
+    }
+
+    Ok(())
+}
+
+/// 写出纯文本字幕（`.txt`），不包含时间戳
+fn write_txt(segments: &[Segment], output_path: &Path) -> Result<()> {
+    let mut file = File::create(output_path).context("无法创建字幕文件")?;
+
+    for segment in segments {
+        writeln!(file, "{}", segment.text)?;
+    }
+
+    Ok(())
+}
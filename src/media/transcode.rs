@@ -0,0 +1,71 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+use std::process::Command;
+
+use crate::utils::get_ffmpeg_path;
+
+/// 转码的目标分辨率
+#[derive(Debug, Clone, Copy)]
+pub enum Resolution {
+    Hd720,
+    Hd1080,
+    Custom(u32, u32),
+}
+
+impl Resolution {
+    /// 解析`--transcode`参数：支持`720p`/`1080p`或`WxH`（如`1280x720`）
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "720p" => Ok(Self::Hd720),
+            "1080p" => Ok(Self::Hd1080),
+            other => {
+                let (w, h) = other
+                    .split_once('x')
+                    .context("分辨率格式应为WxH（如1280x720）或720p/1080p")?;
+                let width: u32 = w.parse().context("无效的宽度")?;
+                let height: u32 = h.parse().context("无效的高度")?;
+                Ok(Self::Custom(width, height))
+            }
+        }
+    }
+
+    fn dimensions(self) -> (u32, u32) {
+        match self {
+            Self::Hd720 => (1280, 720),
+            Self::Hd1080 => (1920, 1080),
+            Self::Custom(width, height) => (width, height),
+        }
+    }
+}
+
+/// 使用ffmpeg将超出目标分辨率的视频预先缩放/归一化，减轻后续音频提取与转录的负担
+///
+/// `mem_limit`透传给ffmpeg的`-rtbufsize`，用于限制处理过程占用的内存（如"8G"）
+pub fn transcode_video(input: &Path, output: &Path, resolution: Resolution, mem_limit: &str) -> Result<()> {
+    let (width, height) = resolution.dimensions();
+    let scale_filter = format!(
+        "scale='min({},iw)':'min({},ih)':force_original_aspect_ratio=decrease",
+        width, height
+    );
+
+    let result = Command::new(get_ffmpeg_path())
+        .args([
+            "-y",
+            "-i",
+            input.to_str().unwrap(),
+            "-rtbufsize",
+            mem_limit,
+            "-vf",
+            &scale_filter,
+            output.to_str().unwrap(),
+        ])
+        .output()
+        .context("无法运行ffmpeg转码命令")?;
+
+    if !result.status.success() {
+        let error = String::from_utf8_lossy(&result.stderr);
+        anyhow::bail!("ffmpeg转码失败: {}", error);
+    }
+
+    Ok(())
+}
@@ -2,10 +2,13 @@ use anyhow::{Context, Result};
 use directories::ProjectDirs;
 use futures_util::StreamExt;
 use indicatif::{ProgressBar, ProgressStyle};
+use sha2::{Digest, Sha256};
 use std::fs;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 
+use crate::config::ModelSourceConfig;
+
 const MODEL_BASE_URL: &str = "https://huggingface.co/ggerganov/whisper.cpp/resolve/main";
 
 /// 获取模型目录路径
@@ -23,60 +26,210 @@ pub fn get_models_dir() -> Result<PathBuf> {
     Ok(models_dir)
 }
 
-/// 检查模型文件是否存在，如果不存在则下载
-pub async fn ensure_model_exists(model_name: &str) -> Result<PathBuf> {
+/// 检查模型文件是否存在，如果不存在则按`source`（未配置时退回内置默认地址）下载
+pub async fn ensure_model_exists(
+    model_name: &str,
+    source: Option<&ModelSourceConfig>,
+) -> Result<PathBuf> {
     let models_dir = get_models_dir()?;
     let model_path = models_dir.join(model_name);
-    
+
     if !model_path.exists() {
         println!("模型文件 {} 不存在，开始下载...", model_name);
-        download_model(model_name, &model_path).await?;
+        download_model(model_name, &model_path, source).await?;
     }
 
     Ok(model_path)
 }
 
-/// 下载模型文件
-async fn download_model(model_name: &str, model_path: &Path) -> Result<()> {
-    let url = format!("{}/{}", MODEL_BASE_URL, model_name);
-    
+/// 按优先级依次尝试的候选下载地址：主地址在前，之后是配置的镜像
+fn candidate_urls(model_name: &str, source: Option<&ModelSourceConfig>) -> Vec<String> {
+    match source {
+        Some(source) => {
+            let mut urls = vec![resolve_source_url(&source.url, source.revision.as_deref(), model_name)];
+            urls.extend(source.mirrors.iter().cloned());
+            urls
+        }
+        None => vec![format!("{}/{}", MODEL_BASE_URL, model_name)],
+    }
+}
+
+/// 解析单条模型来源地址：带`revision`时视为仓库基地址，拼成`{url}/resolve/{revision}/<model_name>`；
+/// 否则视为已经是可直接下载的地址（若以归档/模型文件扩展名结尾）或需要拼接文件名的基地址
+fn resolve_source_url(url: &str, revision: Option<&str>, model_name: &str) -> String {
+    if let Some(revision) = revision {
+        return format!("{}/resolve/{}/{}", url.trim_end_matches('/'), revision, model_name);
+    }
+
+    let looks_like_direct_file = [".bin", ".zip", ".tar", ".tar.gz", ".tgz", ".gz"]
+        .iter()
+        .any(|ext| url.ends_with(ext));
+
+    if looks_like_direct_file {
+        url.to_string()
+    } else {
+        format!("{}/{}", url.trim_end_matches('/'), model_name)
+    }
+}
+
+/// 下载模型文件：依次尝试候选地址，校验SHA-256（若配置），必要时从`.zip`/`.tar`归档中提取出模型
+async fn download_model(
+    model_name: &str,
+    model_path: &Path,
+    source: Option<&ModelSourceConfig>,
+) -> Result<()> {
+    let urls = candidate_urls(model_name, source);
+    let expected_sha256 = source.and_then(|s| s.sha256.as_deref());
+
+    let mut last_error = None;
+    for url in &urls {
+        match try_download_one(url, model_name, model_path, expected_sha256).await {
+            Ok(()) => return Ok(()),
+            Err(e) => {
+                eprintln!("从 {} 下载模型失败: {}，尝试下一个来源", url, e);
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| anyhow::anyhow!("没有可用的模型下载地址")))
+}
+
+/// 从单个地址下载模型（或包含模型的归档），校验并落盘；失败或校验不通过均返回错误，交由调用方重试下一个地址
+async fn try_download_one(
+    url: &str,
+    model_name: &str,
+    model_path: &Path,
+    expected_sha256: Option<&str>,
+) -> Result<()> {
     println!("从 {} 下载模型...", url);
-    
+
+    let temp_path = model_path.with_extension("download");
+    download_to_file(url, &temp_path).await?;
+
+    if let Some(expected) = expected_sha256 {
+        let actual = sha256_of_file(&temp_path)?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            fs::remove_file(&temp_path).ok();
+            anyhow::bail!("SHA-256校验失败: 期望 {}，实际 {}", expected, actual);
+        }
+    }
+
+    if is_archive(url) {
+        extract_model_from_archive(&temp_path, model_name, model_path)?;
+        fs::remove_file(&temp_path).ok();
+    } else {
+        fs::rename(&temp_path, model_path).context("无法写入模型文件")?;
+    }
+
+    Ok(())
+}
+
+/// 下载一个URL的内容到本地文件，带进度条
+async fn download_to_file(url: &str, dest: &Path) -> Result<()> {
     let client = reqwest::Client::new();
-    let res = client
-        .get(&url)
-        .send()
-        .await
-        .context("请求下载模型失败")?;
-    
-    let total_size = res
-        .content_length()
-        .context("无法获取模型文件大小")?;
-    
-    // 创建进度条
+    let res = client.get(url).send().await.context("请求下载模型失败")?;
+
+    let total_size = res.content_length().unwrap_or(0);
+
     let pb = ProgressBar::new(total_size);
     pb.set_style(ProgressStyle::default_bar()
         .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({eta})")
         .context("无法设置进度条样式")?
         .progress_chars("#>-"));
-    
-    // 下载文件
-    let mut file = fs::File::create(model_path).context("无法创建模型文件")?;
+
+    let mut file = fs::File::create(dest).context("无法创建模型文件")?;
     let mut downloaded: u64 = 0;
     let mut stream = res.bytes_stream();
-    
+
     while let Some(item) = stream.next().await {
         let chunk = item.context("下载过程中发生错误")?;
         file.write_all(&chunk).context("写入模型文件失败")?;
         downloaded += chunk.len() as u64;
         pb.set_position(downloaded);
     }
-    
-    pb.finish_with_message(format!("下载完成: {}", model_name));
-    
+
+    pb.finish_with_message("下载完成");
+
+    Ok(())
+}
+
+/// 计算文件的SHA-256十六进制摘要
+fn sha256_of_file(path: &Path) -> Result<String> {
+    let mut file = fs::File::open(path).context("无法打开下载的文件以校验")?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).context("计算SHA-256失败")?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// 根据URL扩展名判断下载内容是否是需要解包的归档
+fn is_archive(url: &str) -> bool {
+    [".zip", ".tar", ".tar.gz", ".tgz"]
+        .iter()
+        .any(|ext| url.ends_with(ext))
+}
+
+/// 从下载的`.zip`/`.tar`（或`.tar.gz`）归档中提取出模型文件，写到`model_path`
+fn extract_model_from_archive(archive_path: &Path, model_name: &str, model_path: &Path) -> Result<()> {
+    if archive_path.to_string_lossy().ends_with(".zip") || is_zip(archive_path)? {
+        let file = fs::File::open(archive_path).context("无法打开下载的压缩包")?;
+        let mut zip = zip::ZipArchive::new(file).context("无法解析zip归档")?;
+
+        let index = (0..zip.len())
+            .find(|i| {
+                zip.by_index(*i)
+                    .map(|entry| entry.name().ends_with(model_name))
+                    .unwrap_or(false)
+            })
+            .context("zip归档中未找到模型文件")?;
+
+        let mut entry = zip.by_index(index).context("无法读取zip归档条目")?;
+        let mut out = fs::File::create(model_path).context("无法创建模型文件")?;
+        std::io::copy(&mut entry, &mut out).context("解压模型文件失败")?;
+    } else {
+        let file = fs::File::open(archive_path).context("无法打开下载的压缩包")?;
+        let reader: Box<dyn std::io::Read> = if has_gzip_magic(archive_path)? {
+            Box::new(flate2::read::GzDecoder::new(file))
+        } else {
+            Box::new(file)
+        };
+        let mut archive = tar::Archive::new(reader);
+
+        let mut entry = archive
+            .entries()
+            .context("无法解析tar归档")?
+            .filter_map(Result::ok)
+            .find(|entry| {
+                entry
+                    .path()
+                    .map(|p| p.ends_with(model_name))
+                    .unwrap_or(false)
+            })
+            .context("tar归档中未找到模型文件")?;
+
+        let mut out = fs::File::create(model_path).context("无法创建模型文件")?;
+        std::io::copy(&mut entry, &mut out).context("解压模型文件失败")?;
+    }
+
     Ok(())
 }
 
+/// 通过文件头的魔数判断是否是zip文件（而不是单纯依赖URL扩展名）
+fn is_zip(path: &Path) -> Result<bool> {
+    let mut file = fs::File::open(path).context("无法打开下载的文件")?;
+    let mut magic = [0u8; 4];
+    let read = file.read(&mut magic).unwrap_or(0);
+    Ok(read >= 4 && &magic == b"PK\x03\x04")
+}
+
+/// 通过文件头的魔数判断tar包是否经过gzip压缩
+fn has_gzip_magic(path: &Path) -> Result<bool> {
+    let mut file = fs::File::open(path).context("无法打开下载的文件")?;
+    let mut magic = [0u8; 2];
+    let read = file.read(&mut magic).unwrap_or(0);
+    Ok(read >= 2 && magic == [0x1f, 0x8b])
+}
+
 /// 获取模型的完整路径
 pub fn get_model_path(model_name: &str) -> Result<PathBuf> {
     let models_dir = get_models_dir()?;
@@ -84,7 +237,10 @@ pub fn get_model_path(model_name: &str) -> Result<PathBuf> {
 }
 
 /// 同步版本的确保模型存在
-pub fn ensure_model_exists_sync(model_name: &str) -> Result<PathBuf> {
+pub fn ensure_model_exists_sync(
+    model_name: &str,
+    source: Option<&ModelSourceConfig>,
+) -> Result<PathBuf> {
     let rt = tokio::runtime::Runtime::new().context("无法创建Tokio运行时")?;
-    rt.block_on(ensure_model_exists(model_name))
+    rt.block_on(ensure_model_exists(model_name, source))
 }
@@ -0,0 +1,116 @@
+use anyhow::{Context, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{SampleFormat, Stream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::media::resample_linear;
+
+/// Whisper所需的采样率
+const TARGET_SAMPLE_RATE: u32 = 16000;
+
+/// 麦克风录音会话：捕获默认输入设备的音频，实时下混为单声道并缓存起来
+pub struct Recorder {
+    stream: Stream,
+    buffer: Arc<Mutex<Vec<f32>>>,
+    recording: Arc<AtomicBool>,
+    source_sample_rate: u32,
+}
+
+impl Recorder {
+    /// 打开默认输入设备并开始录音
+    pub fn start() -> Result<Self> {
+        let host = cpal::default_host();
+        let device = host
+            .default_input_device()
+            .context("未找到可用的麦克风设备")?;
+        let config = device
+            .default_input_config()
+            .context("无法获取麦克风默认配置")?;
+
+        let channels = config.channels() as usize;
+        let source_sample_rate = config.sample_rate().0;
+        let sample_format = config.sample_format();
+
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let recording = Arc::new(AtomicBool::new(true));
+
+        let stream = {
+            let buffer = buffer.clone();
+            let recording = recording.clone();
+            let err_fn = |err| eprintln!("录音流发生错误: {}", err);
+
+            match sample_format {
+                SampleFormat::F32 => device.build_input_stream(
+                    &config.into(),
+                    move |data: &[f32], _| push_samples(&buffer, &recording, data, channels),
+                    err_fn,
+                    None,
+                )?,
+                SampleFormat::I16 => device.build_input_stream(
+                    &config.into(),
+                    move |data: &[i16], _| {
+                        let floats: Vec<f32> =
+                            data.iter().map(|s| *s as f32 / i16::MAX as f32).collect();
+                        push_samples(&buffer, &recording, &floats, channels)
+                    },
+                    err_fn,
+                    None,
+                )?,
+                SampleFormat::U16 => device.build_input_stream(
+                    &config.into(),
+                    move |data: &[u16], _| {
+                        let floats: Vec<f32> = data
+                            .iter()
+                            .map(|s| (*s as f32 / u16::MAX as f32) * 2.0 - 1.0)
+                            .collect();
+                        push_samples(&buffer, &recording, &floats, channels)
+                    },
+                    err_fn,
+                    None,
+                )?,
+                other => anyhow::bail!("不支持的录音采样格式: {:?}", other),
+            }
+        };
+
+        stream.play().context("无法启动录音流")?;
+
+        Ok(Self {
+            stream,
+            buffer,
+            recording,
+            source_sample_rate,
+        })
+    }
+
+    /// 已录制的时长（秒），用于界面显示计时
+    pub fn elapsed_secs(&self) -> f32 {
+        let len = self.buffer.lock().unwrap().len();
+        len as f32 / self.source_sample_rate as f32
+    }
+
+    /// 停止录音，返回重采样到16kHz单声道的PCM样本
+    pub fn stop(self) -> Vec<f32> {
+        self.recording.store(false, Ordering::Relaxed);
+        drop(self.stream);
+
+        let mono_samples = self.buffer.lock().unwrap().clone();
+        resample_linear(&mono_samples, self.source_sample_rate, TARGET_SAMPLE_RATE)
+    }
+}
+
+/// 将一帧多声道数据下混为单声道后追加到缓冲区
+fn push_samples(buffer: &Arc<Mutex<Vec<f32>>>, recording: &Arc<AtomicBool>, data: &[f32], channels: usize) {
+    if !recording.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let mut buffer = buffer.lock().unwrap();
+    if channels <= 1 {
+        buffer.extend_from_slice(data);
+    } else {
+        buffer.extend(data.chunks(channels).map(|frame| {
+            frame.iter().sum::<f32>() / frame.len() as f32
+        }));
+    }
+}
@@ -0,0 +1,78 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+
+use super::Translator;
+use crate::config::TranslateConfig;
+
+/// DeepL官方接口或DeepLX兼容代理
+pub struct DeepLTranslator {
+    api_base: String,
+    api_key: Option<String>,
+}
+
+impl DeepLTranslator {
+    pub fn new(config: &TranslateConfig) -> Self {
+        Self {
+            api_base: config.api_base.clone(),
+            api_key: if config.api_key.is_empty() {
+                None
+            } else {
+                Some(config.api_key.clone())
+            },
+        }
+    }
+}
+
+/// DeepL官方响应：`{"translations": [{"text": "..."}]}`
+/// DeepLX响应：`{"code": 200, "data": "..."}`，两者都兼容解析
+#[derive(Debug, Default, Deserialize)]
+struct DeepLResponse {
+    #[serde(default)]
+    translations: Vec<DeepLTranslation>,
+    #[serde(default)]
+    data: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeepLTranslation {
+    text: String,
+}
+
+#[async_trait]
+impl Translator for DeepLTranslator {
+    async fn translate(&self, lines: &[String], source: &str, target: &str) -> Result<Vec<String>> {
+        let client = reqwest::Client::new();
+        let mut results = Vec::with_capacity(lines.len());
+
+        for line in lines {
+            // DeepL的自动检测靠直接省略`source_lang`字段实现，传"AUTO"会被接口拒绝
+            let mut body = json!({
+                "text": [line],
+                "target_lang": target.to_uppercase(),
+            });
+            if source != "auto" {
+                body["source_lang"] = json!(source.to_uppercase());
+            }
+
+            let mut request = client.post(&self.api_base).json(&body);
+
+            if let Some(key) = &self.api_key {
+                request = request.header("Authorization", format!("DeepL-Auth-Key {}", key));
+            }
+
+            let resp = request.send().await.context("请求DeepL翻译失败")?;
+            let body: DeepLResponse = resp.json().await.context("解析DeepL翻译响应失败")?;
+
+            let translated = body
+                .data
+                .or_else(|| body.translations.into_iter().next().map(|t| t.text))
+                .unwrap_or_default();
+
+            results.push(translated);
+        }
+
+        Ok(results)
+    }
+}
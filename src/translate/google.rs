@@ -0,0 +1,60 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+
+use super::Translator;
+use crate::config::TranslateConfig;
+
+/// Google翻译（`translate_a/single` 免key接口）
+pub struct GoogleTranslator {
+    api_base: String,
+}
+
+impl GoogleTranslator {
+    pub fn new(config: &TranslateConfig) -> Self {
+        let api_base = if config.api_base.is_empty() {
+            "https://translate.googleapis.com/translate_a/single".to_string()
+        } else {
+            config.api_base.clone()
+        };
+
+        Self { api_base }
+    }
+}
+
+#[async_trait]
+impl Translator for GoogleTranslator {
+    async fn translate(&self, lines: &[String], source: &str, target: &str) -> Result<Vec<String>> {
+        let client = reqwest::Client::new();
+        let mut results = Vec::with_capacity(lines.len());
+
+        for line in lines {
+            let resp = client
+                .get(&self.api_base)
+                .query(&[
+                    ("client", "gtx"),
+                    ("sl", source),
+                    ("tl", target),
+                    ("dt", "t"),
+                    ("q", line.as_str()),
+                ])
+                .send()
+                .await
+                .context("请求Google翻译失败")?;
+
+            let body: serde_json::Value = resp.json().await.context("解析Google翻译响应失败")?;
+            // Google会把输入按句子/换行拆成多个分段分别翻译，用"\n"拼接以保留原文的行结构，
+            // 否则上层`translate_segments`拼接多行后插入的`BATCH_SEPARATOR`会被直接吞掉。
+            let translated = body[0]
+                .as_array()
+                .context("Google翻译响应格式异常")?
+                .iter()
+                .filter_map(|seg| seg[0].as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            results.push(translated);
+        }
+
+        Ok(results)
+    }
+}
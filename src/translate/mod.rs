@@ -0,0 +1,104 @@
+mod deepl;
+mod google;
+mod openai;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub use deepl::DeepLTranslator;
+pub use google::GoogleTranslator;
+pub use openai::OpenAiTranslator;
+
+use crate::config::TranslateConfig;
+
+/// 字幕翻译后端的统一抽象，每种后端（Google/DeepL/OpenAI兼容接口等）各自实现
+#[async_trait]
+pub trait Translator: Send + Sync {
+    async fn translate(&self, lines: &[String], source: &str, target: &str) -> Result<Vec<String>>;
+}
+
+/// 根据配置创建对应的翻译后端
+pub fn create_translator(config: &TranslateConfig) -> Result<Box<dyn Translator>> {
+    match config.backend.as_str() {
+        "google" => Ok(Box::new(GoogleTranslator::new(config))),
+        "deepl" | "deeplx" => Ok(Box::new(DeepLTranslator::new(config))),
+        "openai" => Ok(Box::new(OpenAiTranslator::new(config))),
+        other => anyhow::bail!("不支持的翻译后端: {}", other),
+    }
+}
+
+/// 批量翻译时用于拼接/拆分多段文本的分隔符
+const BATCH_SEPARATOR: &str = "\n<<<SEG>>>\n";
+/// 每批翻译的段落数量上限，用于减少API请求次数
+const BATCH_SIZE: usize = 20;
+
+/// 将字幕段落分批交给翻译后端处理，并通过回调汇报进度（0.0~1.0）
+///
+/// 为了减少请求次数，每批会把多个段落用分隔符拼接后一次性翻译，再按分隔符拆回。
+/// 如果某一批返回的行数与原文对不上（后端重排了分隔符周围的空白/标点等情况），
+/// 该批会回退为逐行单独翻译，仍然对不上或请求失败的单行才最终留空，
+/// 以保证译文数量始终和原文一一对应，不破坏字幕时间轴。
+pub async fn translate_segments<F>(
+    translator: &dyn Translator,
+    lines: &[String],
+    source: &str,
+    target: &str,
+    mut on_progress: F,
+) -> Result<Vec<String>>
+where
+    F: FnMut(f32),
+{
+    let mut translated = Vec::with_capacity(lines.len());
+    let batches: Vec<&[String]> = lines.chunks(BATCH_SIZE).collect();
+    let total_batches = batches.len().max(1);
+
+    for (batch_index, batch) in batches.iter().enumerate() {
+        let joined = batch.join(BATCH_SEPARATOR);
+        let result = translator.translate(&[joined], source, target).await?;
+        let combined = result.join(BATCH_SEPARATOR);
+        let parts: Vec<String> = combined.split(BATCH_SEPARATOR).map(str::to_string).collect();
+
+        let parts = if parts.len() != batch.len() {
+            eprintln!(
+                "翻译批次返回{}行，与原文{}行不一致，回退为逐行单独翻译",
+                parts.len(),
+                batch.len()
+            );
+            translate_batch_per_line(translator, batch, source, target).await
+        } else {
+            parts
+        };
+
+        translated.extend(parts);
+        on_progress((batch_index + 1) as f32 / total_batches as f32);
+    }
+
+    Ok(translated)
+}
+
+/// 逐行单独翻译一个批次，单行翻译失败或仍返回异常结果时才留空，避免整批被丢弃
+async fn translate_batch_per_line(
+    translator: &dyn Translator,
+    batch: &[String],
+    source: &str,
+    target: &str,
+) -> Vec<String> {
+    let mut parts = Vec::with_capacity(batch.len());
+
+    for line in batch {
+        let single = translator.translate(std::slice::from_ref(line), source, target).await;
+        match single {
+            Ok(mut result) if result.len() == 1 => parts.push(result.remove(0)),
+            Ok(_) => {
+                eprintln!("单行翻译返回异常结果，保留空字符串: {:?}", line);
+                parts.push(String::new());
+            }
+            Err(e) => {
+                eprintln!("单行翻译失败，保留空字符串: {} ({:?})", e, line);
+                parts.push(String::new());
+            }
+        }
+    }
+
+    parts
+}
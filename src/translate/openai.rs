@@ -0,0 +1,66 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::json;
+
+use super::Translator;
+use crate::config::TranslateConfig;
+
+/// OpenAI兼容的Chat Completions接口（官方ChatGPT接口或任何实现同一协议的代理）
+pub struct OpenAiTranslator {
+    api_base: String,
+    api_key: String,
+    model: String,
+}
+
+impl OpenAiTranslator {
+    pub fn new(config: &TranslateConfig) -> Self {
+        let api_base = if config.api_base.is_empty() {
+            "https://api.openai.com/v1/chat/completions".to_string()
+        } else {
+            config.api_base.clone()
+        };
+
+        Self {
+            api_base,
+            api_key: config.api_key.clone(),
+            model: "gpt-4o-mini".to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl Translator for OpenAiTranslator {
+    async fn translate(&self, lines: &[String], source: &str, target: &str) -> Result<Vec<String>> {
+        let client = reqwest::Client::new();
+        let mut results = Vec::with_capacity(lines.len());
+
+        for line in lines {
+            let prompt = format!(
+                "将下面的字幕文本从{}翻译成{}，保持原有的行数和顺序，只返回翻译结果，不要添加任何解释：\n{}",
+                source, target, line
+            );
+
+            let resp = client
+                .post(&self.api_base)
+                .bearer_auth(&self.api_key)
+                .json(&json!({
+                    "model": self.model,
+                    "messages": [{"role": "user", "content": prompt}],
+                }))
+                .send()
+                .await
+                .context("请求OpenAI翻译接口失败")?;
+
+            let body: serde_json::Value = resp.json().await.context("解析OpenAI翻译响应失败")?;
+            let translated = body["choices"][0]["message"]["content"]
+                .as_str()
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+
+            results.push(translated);
+        }
+
+        Ok(results)
+    }
+}
@@ -1,3 +1,5 @@
+use anyhow::{Context, Result};
+use directories::ProjectDirs;
 use std::path::Path;
 
 /// 获取资源文件路径，兼容开发环境和打包后的环境
@@ -60,6 +62,62 @@ pub fn read_resource_file(resource_path: &str) -> Result<Vec<u8>, std::io::Error
     std::fs::read(path)
 }
 
+/// 展开输出路径模板中的占位符，得到与输入文件相关的具体路径
+///
+/// 支持的占位符：
+/// - `?video`：输入文件所在目录
+/// - `?name`：输入文件的文件名（不含扩展名）
+/// - `?config`：用户级配置目录
+/// - `?temp`：系统临时目录
+///
+/// # 示例
+///
+/// ```
+/// use std::path::Path;
+/// use subtitle_generator::utils::resolve_output_template;
+///
+/// let output = resolve_output_template("?video/subs/?name.srt", Path::new("/movies/a.mp4")).unwrap();
+/// assert_eq!(output, "/movies/subs/a.srt");
+/// ```
+pub fn resolve_output_template(template: &str, input_path: &Path) -> Result<String> {
+    let mut result = template.to_string();
+
+    // 每个占位符只在模板中实际出现时才解析，避免为一个普通的字面量路径
+    // （不含?config）去强制要求`ProjectDirs`能确定用户主目录/配置目录
+    if result.contains("?video") {
+        let video_dir = input_path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(|dir| dir.to_string_lossy().to_string())
+            .unwrap_or_else(|| ".".to_string());
+        result = result.replace("?video", &video_dir);
+    }
+
+    if result.contains("?name") {
+        let name = input_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "output".to_string());
+        result = result.replace("?name", &name);
+    }
+
+    if result.contains("?config") {
+        let config_dir = ProjectDirs::from("", "", "subtitle-generator")
+            .context("无法确定用户配置目录")?
+            .config_dir()
+            .to_string_lossy()
+            .to_string();
+        result = result.replace("?config", &config_dir);
+    }
+
+    if result.contains("?temp") {
+        let temp_dir = std::env::temp_dir().to_string_lossy().to_string();
+        result = result.replace("?temp", &temp_dir);
+    }
+
+    Ok(result)
+}
+
 /// 获取ffmpeg可执行文件的路径
 ///
 /// 首先尝试在应用包内的可执行文件目录查找，
@@ -0,0 +1,3 @@
+mod transcribe;
+
+pub use transcribe::{transcribe_audio, transcribe_audio_with_progress, transcribe_samples};
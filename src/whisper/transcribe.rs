@@ -1,24 +1,103 @@
 use anyhow::{Context, Result};
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
-use crate::media::{extract_audio_from_video, generate_srt_file, parse_wav_file};
+use crate::config::{ModelSourceConfig, TranslateConfig};
+use crate::media::{
+    decode_audio_file, extract_audio_from_video, generate_subtitles_translated, parse_wav_file,
+    SubtitleFormat,
+};
 use std::env;
 use std::fs;
 
+/// 无需经过ffmpeg预转码、可直接解码的纯音频扩展名
+const DIRECT_AUDIO_EXTENSIONS: [&str; 4] = ["mp3", "flac", "ogg", "wav"];
+
 /// 核心功能：从视频生成字幕
-pub fn transcribe_audio(input: &str, output: &str, model: &str, language: &str) -> Result<()> {
+pub fn transcribe_audio(
+    input: &str,
+    output: &str,
+    model: &str,
+    language: &str,
+    ass_style: Option<&str>,
+    translate_config: &TranslateConfig,
+    model_source: Option<&ModelSourceConfig>,
+) -> Result<()> {
+    transcribe_audio_with_progress(
+        input,
+        output,
+        model,
+        language,
+        ass_style,
+        translate_config,
+        model_source,
+        Arc::new(AtomicBool::new(false)),
+        |_progress| {},
+        |_text| {},
+    )
+}
+
+/// 从视频生成字幕，并在转录过程中汇报真实进度与逐段文本预览
+///
+/// `translate_config`/`model_source`应为调用方已解析好的分层配置（含`--config`覆盖项），
+/// 不在本函数内部重新加载默认路径的配置文件。
+///
+/// `abort_flag`在转录期间被whisper-rs的中止回调轮询，置为true时会尽快停止当前转录。
+pub fn transcribe_audio_with_progress(
+    input: &str,
+    output: &str,
+    model: &str,
+    language: &str,
+    ass_style: Option<&str>,
+    translate_config: &TranslateConfig,
+    model_source: Option<&ModelSourceConfig>,
+    abort_flag: Arc<AtomicBool>,
+    mut on_progress: impl FnMut(i32) + Send + 'static,
+    mut on_segment: impl FnMut(&str) + Send + 'static,
+) -> Result<()> {
     let video_path = Path::new(input);
     if !video_path.exists() {
         anyhow::bail!("视频文件不存在: {}", input);
     }
 
-    // 确保模型存在，如果不存在则下载
+    // 展开输出路径模板中的?video/?name/?config/?temp占位符
+    let output = crate::utils::resolve_output_template(output, video_path)?;
+    let output = output.as_str();
+    if let Some(parent) = Path::new(output).parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent).context("无法创建字幕输出目录")?;
+        }
+    }
+
+    // 确保模型存在，如果不存在则按配置的模型来源（或内置默认地址）下载
     println!("检查模型: {}...", model);
-    let model_path = super::model::check_model_sync(model)?;
+    let model_path = crate::model::ensure_model_exists_sync(model, model_source)?;
     println!("使用模型: {:?}", model_path);
 
-    let output_path = Path::new(output);
+    let is_direct_audio = video_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| DIRECT_AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+        .unwrap_or(false);
+
+    if is_direct_audio {
+        println!("正在直接解码音频文件...");
+        let samples = decode_audio_file(video_path)?;
+
+        return transcribe_samples(
+            &samples,
+            output,
+            model_path.to_string_lossy().as_ref(),
+            language,
+            ass_style,
+            translate_config,
+            abort_flag,
+            on_progress,
+            on_segment,
+        );
+    }
 
     // 在系统临时目录创建临时WAV文件
     let temp_dir = env::temp_dir();
@@ -34,12 +113,46 @@ pub fn transcribe_audio(input: &str, output: &str, model: &str, language: &str)
     whisper_rs::convert_integer_to_float_audio(&original_samples, &mut samples)
         .context("无法转换音频样本")?;
 
-    println!("正在加载Whisper模型...");
-    let ctx = WhisperContext::new_with_params(
+    let result = transcribe_samples(
+        &samples,
+        output,
         model_path.to_string_lossy().as_ref(),
-        WhisperContextParameters::default(),
-    )
-    .context("无法加载Whisper模型")?;
+        language,
+        ass_style,
+        translate_config,
+        abort_flag,
+        on_progress,
+        on_segment,
+    );
+
+    // 清理临时文件
+    if temp_audio_path.exists() {
+        fs::remove_file(&temp_audio_path).context("无法删除临时音频文件")?;
+    }
+
+    result
+}
+
+/// 核心功能：直接从16kHz单声道f32 PCM样本生成字幕
+///
+/// 视频转录、麦克风录音转录最终都汇聚到这里：前者先把视频转成PCM样本，
+/// 后者直接采集PCM样本，二者都复用同一套Whisper推理与字幕生成逻辑。
+pub fn transcribe_samples(
+    samples: &[f32],
+    output: &str,
+    model_path: &str,
+    language: &str,
+    ass_style: Option<&str>,
+    translate_config: &TranslateConfig,
+    abort_flag: Arc<AtomicBool>,
+    mut on_progress: impl FnMut(i32) + Send + 'static,
+    mut on_segment: impl FnMut(&str) + Send + 'static,
+) -> Result<()> {
+    let output_path = Path::new(output);
+
+    println!("正在加载Whisper模型...");
+    let ctx = WhisperContext::new_with_params(model_path, WhisperContextParameters::default())
+        .context("无法加载Whisper模型")?;
 
     println!("正在转录音频...");
     let mut state = ctx.create_state().context("无法创建状态")?;
@@ -48,22 +161,43 @@ pub fn transcribe_audio(input: &str, output: &str, model: &str, language: &str)
     if language != "auto" {
         params.set_language(Some(language));
     }
-    params.set_progress_callback_safe(|progress| println!("处理进度: {}%", progress));
+
+    params.set_progress_callback_safe(move |progress| {
+        println!("处理进度: {}%", progress);
+        on_progress(progress);
+    });
+
+    params.set_segment_callback_safe(move |segment| {
+        on_segment(segment.text.trim());
+    });
+
+    params.set_abort_callback_safe({
+        let abort_flag = abort_flag.clone();
+        move || abort_flag.load(Ordering::Relaxed)
+    });
 
     let start_time = std::time::Instant::now();
 
-    state.full(params, &samples).context("转录失败")?;
+    state.full(params, samples).context("转录失败")?;
+
+    if abort_flag.load(Ordering::Relaxed) {
+        anyhow::bail!("转录已取消");
+    }
 
     let elapsed = start_time.elapsed();
     println!("转录完成，耗时 {}ms", elapsed.as_millis());
 
     println!("正在生成字幕文件...");
-    generate_srt_file(&state, output_path)?;
-
-    // 清理临时文件
-    if temp_audio_path.exists() {
-        fs::remove_file(&temp_audio_path).context("无法删除临时音频文件")?;
-    }
+    let format = SubtitleFormat::from_path(output_path);
+
+    let rt = tokio::runtime::Runtime::new().context("无法创建Tokio运行时")?;
+    rt.block_on(generate_subtitles_translated(
+        &state,
+        output_path,
+        format,
+        translate_config,
+        ass_style,
+    ))?;
 
     println!("字幕生成完成: {}", output);
 